@@ -1,10 +1,16 @@
-use crate::crawler::get_project_files;
-use crate::graph::build_graph;
+use crate::crawler::FileCollector;
+use crate::dir_contents::DirContents;
+use crate::graph::build_graph_with_aliases;
+use crate::manifest::load_manifest;
+use crate::project_type::{detect_project_types, ProjectType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
+/// Extensions `build_graph` actually parses for imports; no point crawling
+/// anything else into the context.
+const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "rs"];
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PackageJson {
     pub name: Option<String>,
@@ -26,25 +32,63 @@ pub struct FileContext {
 pub struct ProjectContext {
     pub files: Vec<FileContext>,
     pub package_json: Option<PackageJson>,
-    // In the future: main_files, project_type, etc.
+    /// Import cycles found in the dependency graph, each as the ordered list
+    /// of file paths forming the cycle, starting at its lexicographically
+    /// smallest path (which is not repeated at the end).
+    pub cycles: Vec<Vec<String>>,
+    /// Languages/ecosystems detected in the workspace; a project can be
+    /// polyglot, so this is a list rather than a single guess.
+    pub project_type: Vec<ProjectType>,
+    // In the future: main_files, etc.
 }
 
 pub fn get_project_context(root_path: &str) -> ProjectContext {
-    let files = get_project_files(root_path);
-    let file_paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
     let root = Path::new(root_path);
-    
-    let graph = build_graph(&file_paths, root);
-    
-    // Parse package.json
-    let package_json_path = root.join("package.json");
-    let package_json = if package_json_path.exists() {
-        let content = fs::read_to_string(package_json_path).unwrap_or_default();
-        serde_json::from_str(&content).ok()
-    } else {
-        None
+    let dir_contents = DirContents::collect(root);
+    let manifest = load_manifest(&dir_contents);
+
+    let extensions: Vec<String> = manifest
+        .as_ref()
+        .and_then(|m| m.extensions.clone())
+        .unwrap_or_else(|| SOURCE_EXTENSIONS.iter().map(|s| s.to_string()).collect());
+    let exclude_globs = manifest.as_ref().and_then(|m| m.exclude.clone()).unwrap_or_default();
+
+    let collector = || {
+        FileCollector::new()
+            .ignore_node_modules()
+            .ignore_git_folder()
+            .exclude_root(root_path)
+            .add_exclude_globs(exclude_globs.clone())
+            .add_extensions(extensions.clone())
     };
 
+    let files = match manifest.as_ref().and_then(|m| m.roots.clone()) {
+        Some(roots) if !roots.is_empty() => roots
+            .iter()
+            .flat_map(|source_root| {
+                let sub_root = root.join(source_root);
+                collector()
+                    .collect(&sub_root.to_string_lossy())
+                    .into_iter()
+                    .map(|mut file| {
+                        file.path = Path::new(source_root).join(&file.path);
+                        file
+                    })
+            })
+            .collect(),
+        _ => collector().collect(root_path),
+    };
+
+    let file_paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
+    let manual_aliases = manifest.as_ref().and_then(|m| m.aliases.clone()).unwrap_or_default();
+
+    let graph = build_graph_with_aliases(&file_paths, root, &manual_aliases, &dir_contents);
+
+    // Parse package.json
+    let package_json = dir_contents
+        .read(Path::new("package.json"))
+        .and_then(|content| serde_json::from_str(content.as_str()).ok());
+
     // Convert graph to serializable context
     let mut file_contexts = Vec::new();
     
@@ -77,8 +121,13 @@ pub fn get_project_context(root_path: &str) -> ProjectContext {
         });
     }
 
+    let cycles = graph.find_cycles();
+    let project_type = detect_project_types(root);
+
     ProjectContext {
         files: file_contexts,
         package_json,
+        cycles,
+        project_type,
     }
 }
@@ -1,5 +1,8 @@
+use ignore::overrides::OverrideBuilder;
 use ignore::WalkBuilder;
 use std::path::PathBuf;
+#[cfg(test)]
+use std::path::Path;
 
 #[derive(Debug, Clone)]
 pub struct FileInfo {
@@ -7,39 +10,237 @@ pub struct FileInfo {
     pub extension: Option<String>,
 }
 
-pub fn get_project_files(root_path: &str) -> Vec<FileInfo> {
-    let mut files = Vec::new();
-    let walker = WalkBuilder::new(root_path)
-        .hidden(false) // Allow hidden files (like .env), gitignore will still handle .git
-        .git_ignore(true)
-        .build();
-
-    for result in walker {
-        match result {
-            Ok(entry) => {
-                if entry.file_type().map_or(false, |ft| ft.is_file()) {
+/// Builds up a file walk before running it, so callers can opt into
+/// `node_modules`/`.git` pruning and exclude globs instead of getting one
+/// hardcoded `WalkBuilder` config.
+///
+/// Excludes are matched while traversing (via `ignore`'s override matcher),
+/// so an excluded directory is pruned as a whole subtree rather than walked
+/// and filtered afterward.
+pub struct FileCollector {
+    ignore_node_modules: bool,
+    ignore_git_folder: bool,
+    respect_gitignore: bool,
+    exclude_globs: Vec<String>,
+    exclude_root: Option<String>,
+    extensions: Option<Vec<String>>,
+}
+
+impl FileCollector {
+    pub fn new() -> Self {
+        Self {
+            ignore_node_modules: false,
+            ignore_git_folder: false,
+            respect_gitignore: true,
+            exclude_globs: Vec::new(),
+            exclude_root: None,
+            extensions: None,
+        }
+    }
+
+    pub fn ignore_node_modules(mut self) -> Self {
+        self.ignore_node_modules = true;
+        self
+    }
+
+    pub fn ignore_git_folder(mut self) -> Self {
+        self.ignore_git_folder = true;
+        self
+    }
+
+    /// Whether to prune paths matched by the project's own `.gitignore`
+    /// (default `true`). `node_modules` is gitignored in virtually every
+    /// JS/TS project, so callers that need to see into it regardless of
+    /// `.gitignore` — e.g. resolving `node_modules` membership — should
+    /// pass `false` here rather than relying on `ignore_node_modules`.
+    pub fn respect_gitignore(mut self, respect: bool) -> Self {
+        self.respect_gitignore = respect;
+        self
+    }
+
+    /// Anchor exclude globs against `root` instead of whatever path is later
+    /// passed to `collect`. Exclude globs are written relative to the
+    /// project root, so when crawling one of several manifest `roots`
+    /// individually, the globs still need to match against the project root
+    /// rather than being re-rooted under each sub-root.
+    pub fn exclude_root(mut self, root: &str) -> Self {
+        self.exclude_root = Some(root.to_string());
+        self
+    }
+
+    pub fn add_exclude_globs<I, S>(mut self, globs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude_globs.extend(globs.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn add_extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extensions
+            .get_or_insert_with(Vec::new)
+            .extend(extensions.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn collect(&self, root_path: &str) -> Vec<FileInfo> {
+        let overrides_root = self.exclude_root.as_deref().unwrap_or(root_path);
+        let mut overrides = OverrideBuilder::new(overrides_root);
+        if self.ignore_node_modules {
+            overrides
+                .add("!node_modules")
+                .expect("static override glob is valid");
+        }
+        if self.ignore_git_folder {
+            overrides.add("!.git").expect("static override glob is valid");
+        }
+        for glob in &self.exclude_globs {
+            let pattern = format!("!{glob}");
+            if let Err(err) = overrides.add(&pattern) {
+                eprintln!("Invalid exclude glob '{glob}': {err}");
+            }
+        }
+        let overrides = overrides
+            .build()
+            .expect("override patterns were validated above");
+
+        let mut builder = WalkBuilder::new(root_path);
+        builder
+            .hidden(false) // Allow hidden files (like .env); .git is only pruned via `ignore_git_folder`
+            .git_ignore(self.respect_gitignore)
+            .overrides(overrides);
+
+        let mut files = Vec::new();
+        for result in builder.build() {
+            match result {
+                Ok(entry) => {
+                    if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+                        continue;
+                    }
+
                     let path = entry.path();
+                    let extension = path.extension().map(|s| s.to_string_lossy().to_string());
+
+                    if let Some(extensions) = &self.extensions {
+                        let keep = extension
+                            .as_deref()
+                            .is_some_and(|ext| extensions.iter().any(|e| e == ext));
+                        if !keep {
+                            continue;
+                        }
+                    }
+
                     // Get path relative to root if possible
                     let rel_path = match path.strip_prefix(root_path) {
                         Ok(p) => p.to_path_buf(),
                         Err(_) => path.to_path_buf(),
                     };
 
-                    // Skip .git folder explicitly if ignore crate doesn't catch it for some reason
-                    // (WalkBuilder usually handles this via git_ignore(true) but being safe)
-                    if rel_path.components().any(|c| c.as_os_str() == ".git") {
-                        continue;
-                    }
-
                     files.push(FileInfo {
-                        path: rel_path.clone(),
-                        extension: path.extension().map(|s| s.to_string_lossy().to_string()),
+                        path: rel_path,
+                        extension,
                     });
                 }
+                Err(err) => eprintln!("Error walking directory: {}", err),
             }
-            Err(err) => eprintln!("Error walking directory: {}", err),
         }
+
+        files
+    }
+}
+
+impl Default for FileCollector {
+    fn default() -> Self {
+        Self::new()
     }
+}
+
+pub fn get_project_files(root_path: &str) -> Vec<FileInfo> {
+    FileCollector::new()
+        .ignore_node_modules()
+        .ignore_git_folder()
+        .collect(root_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static TEST_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    /// Build a throwaway project under the system temp dir with the given
+    /// files, returning its root. Callers are responsible for cleaning up
+    /// via `fs::remove_dir_all`.
+    fn project(files: &[(&str, &str)]) -> PathBuf {
+        let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let root = std::env::temp_dir().join(format!("doctype_crawler_test_{}_{id}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        for (relative_path, content) in files {
+            let full_path = root.join(relative_path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent).unwrap();
+            }
+            fs::write(full_path, content).unwrap();
+        }
 
-    files
+        root
+    }
+
+    fn collected_paths(root: &Path, collector: FileCollector) -> Vec<PathBuf> {
+        collector
+            .collect(&root.to_string_lossy())
+            .into_iter()
+            .map(|f| f.path)
+            .collect()
+    }
+
+    #[test]
+    fn prunes_node_modules_as_a_whole_subtree() {
+        let root = project(&[
+            ("src/a.ts", ""),
+            ("node_modules/lodash/index.js", ""),
+            ("node_modules/lodash/package.json", ""),
+        ]);
+
+        let paths = collected_paths(&root, FileCollector::new().ignore_node_modules());
+
+        assert!(paths.contains(&PathBuf::from("src/a.ts")));
+        assert!(!paths.iter().any(|p| p.starts_with("node_modules")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn prunes_custom_exclude_globs_as_a_whole_subtree() {
+        let root = project(&[("src/a.ts", ""), ("dist/bundle.js", ""), ("dist/bundle.js.map", "")]);
+
+        let paths = collected_paths(&root, FileCollector::new().add_exclude_globs(["dist/**"]));
+
+        assert!(paths.contains(&PathBuf::from("src/a.ts")));
+        assert!(!paths.iter().any(|p| p.starts_with("dist")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn exclude_root_anchors_globs_to_a_different_directory_than_the_walk() {
+        let root = project(&[("apps/api/src/a.ts", ""), ("apps/api/fixtures/data.json", "")]);
+        let sub_root = root.join("apps/api");
+
+        let collector = FileCollector::new()
+            .exclude_root(&root.to_string_lossy())
+            .add_exclude_globs(["apps/api/fixtures/**"]);
+        let paths = collected_paths(&sub_root, collector);
+
+        assert!(paths.contains(&PathBuf::from("src/a.ts")));
+        assert!(!paths.iter().any(|p| p.starts_with("fixtures")));
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
@@ -0,0 +1,99 @@
+use crate::crawler::FileCollector;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// A single crawl of the project tree, kept around so every later pass
+/// (import resolution, graph building, manifest parsing) can query it
+/// in-memory instead of re-walking the filesystem or re-reading a file it
+/// already read.
+///
+/// Unlike the file list used for graph nodes, this crawl neither prunes
+/// `node_modules` nor respects the project's own `.gitignore` — `ignore`
+/// applies `.gitignore` as soon as a `.git` directory exists anywhere up the
+/// tree, and `node_modules` is gitignored in virtually every JS/TS project,
+/// so honoring it here would make the resolver blind to the very directory
+/// it needs to resolve bare specifiers against.
+pub struct DirContents {
+    root: PathBuf,
+    files: HashSet<PathBuf>,
+    dirs: HashSet<PathBuf>,
+    by_extension: HashMap<String, Vec<PathBuf>>,
+    contents: RefCell<HashMap<PathBuf, Rc<String>>>,
+}
+
+impl DirContents {
+    pub fn collect(root: &Path) -> Self {
+        let entries = FileCollector::new()
+            .ignore_git_folder()
+            .respect_gitignore(false)
+            .collect(&root.to_string_lossy());
+
+        let mut files = HashSet::with_capacity(entries.len());
+        let mut dirs = HashSet::new();
+        let mut by_extension: HashMap<String, Vec<PathBuf>> = HashMap::new();
+
+        for entry in entries {
+            if let Some(ext) = &entry.extension {
+                by_extension.entry(ext.clone()).or_default().push(entry.path.clone());
+            }
+
+            let mut ancestor = entry.path.parent();
+            while let Some(dir) = ancestor {
+                if dir.as_os_str().is_empty() || !dirs.insert(dir.to_path_buf()) {
+                    break;
+                }
+                ancestor = dir.parent();
+            }
+
+            files.insert(entry.path);
+        }
+
+        Self {
+            root: root.to_path_buf(),
+            files,
+            dirs,
+            by_extension,
+            contents: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// O(1) membership check for a path relative to `root`, replacing the
+    /// old approach of probing the filesystem (or the graph's `node_map`)
+    /// once per resolution candidate.
+    pub fn contains(&self, relative_path: &Path) -> bool {
+        self.files.contains(relative_path)
+    }
+
+    /// O(1) membership check for a directory relative to `root`, derived
+    /// from the parents of every crawled file. Lets callers like the
+    /// resolver's `node_modules` lookup check "does this directory exist"
+    /// without a direct filesystem `is_dir` call for every unresolved bare
+    /// specifier (including non-installed built-ins like `fs`/`path`).
+    pub fn contains_dir(&self, relative_path: &Path) -> bool {
+        self.dirs.contains(relative_path)
+    }
+
+    pub fn files_with_extension(&self, extension: &str) -> &[PathBuf] {
+        self.by_extension
+            .get(extension)
+            .map(|files| files.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Read a file's contents relative to `root`, caching the result so it's
+    /// never read from disk more than once per run.
+    pub fn read(&self, relative_path: &Path) -> Option<Rc<String>> {
+        if let Some(cached) = self.contents.borrow().get(relative_path) {
+            return Some(cached.clone());
+        }
+
+        let content = Rc::new(fs::read_to_string(self.root.join(relative_path)).ok()?);
+        self.contents
+            .borrow_mut()
+            .insert(relative_path.to_path_buf(), content.clone());
+        Some(content)
+    }
+}
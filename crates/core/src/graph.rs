@@ -1,13 +1,17 @@
+use crate::dir_contents::DirContents;
+use crate::resolver::Resolver;
 use petgraph::graph::{DiGraph, NodeIndex};
 use regex::Regex;
 use std::collections::HashMap;
-use std::fs;
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub struct FileNode {
     pub path: PathBuf,
     pub name: String,
+    /// True if this node lives outside the project (e.g. resolved into
+    /// `node_modules`) rather than being one of the crawled source files.
+    pub external: bool,
 }
 
 pub struct ProjectGraph {
@@ -15,6 +19,12 @@ pub struct ProjectGraph {
     pub node_map: HashMap<PathBuf, NodeIndex>,
 }
 
+impl Default for ProjectGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ProjectGraph {
     pub fn new() -> Self {
         Self {
@@ -24,6 +34,10 @@ impl ProjectGraph {
     }
 
     pub fn add_file(&mut self, path: PathBuf) -> NodeIndex {
+        self.add_file_with_kind(path, false)
+    }
+
+    pub fn add_file_with_kind(&mut self, path: PathBuf, external: bool) -> NodeIndex {
         if let Some(&idx) = self.node_map.get(&path) {
             return idx;
         }
@@ -36,27 +50,151 @@ impl ProjectGraph {
         let node = self.graph.add_node(FileNode {
             path: path.clone(),
             name,
+            external,
         });
         self.node_map.insert(path, node);
         node
     }
 
     pub fn add_dependency(&mut self, from: PathBuf, to: PathBuf) {
+        self.add_dependency_with_kind(from, to, false)
+    }
+
+    pub fn add_dependency_with_kind(&mut self, from: PathBuf, to: PathBuf, to_external: bool) {
         let from_idx = self.add_file(from);
-        let to_idx = self.add_file(to);
+        let to_idx = self.add_file_with_kind(to, to_external);
         self.graph.update_edge(from_idx, to_idx, ());
     }
+
+    /// Find import cycles in the graph, deduplicated by rotating each cycle
+    /// so its lexicographically smallest path comes first.
+    ///
+    /// Walks the graph depth-first, tracking which nodes are on the current
+    /// import stack ("visiting") and which are fully explored ("finished").
+    /// An edge into a node still on the stack closes a cycle; the cycle is
+    /// the slice of the stack from that node to the top. Edges into finished
+    /// nodes are not followed again, so a cycle that is only reachable
+    /// through an already-finished node is not reported.
+    ///
+    /// Uses an explicit work stack rather than native recursion — this
+    /// series targets large monorepos, and a long linear import chain would
+    /// otherwise blow the call stack.
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        let mut visiting = vec![false; self.graph.node_count()];
+        let mut finished = vec![false; self.graph.node_count()];
+        let mut cycles = Vec::new();
+
+        for start in self.graph.node_indices() {
+            if finished[start.index()] {
+                continue;
+            }
+
+            // `call_stack` mirrors the recursive call stack: each frame is
+            // the node being visited, its outgoing neighbors, and how many
+            // of them have been processed so far. `path_stack` mirrors the
+            // current import stack used to slice out a cycle.
+            let mut call_stack: Vec<(NodeIndex, Vec<NodeIndex>, usize)> = Vec::new();
+            let mut path_stack: Vec<NodeIndex> = Vec::new();
+
+            visiting[start.index()] = true;
+            path_stack.push(start);
+            call_stack.push((
+                start,
+                self.graph
+                    .neighbors_directed(start, petgraph::Direction::Outgoing)
+                    .collect(),
+                0,
+            ));
+
+            while let Some(frame_idx) = call_stack.len().checked_sub(1) {
+                let next_idx = call_stack[frame_idx].2;
+
+                if next_idx >= call_stack[frame_idx].1.len() {
+                    let (node, _, _) = call_stack.pop().unwrap();
+                    path_stack.pop();
+                    visiting[node.index()] = false;
+                    finished[node.index()] = true;
+                    continue;
+                }
+
+                let neighbor = call_stack[frame_idx].1[next_idx];
+                call_stack[frame_idx].2 += 1;
+
+                if visiting[neighbor.index()] {
+                    let pos = path_stack.iter().position(|&n| n == neighbor).unwrap_or(0);
+                    let cycle = path_stack[pos..]
+                        .iter()
+                        .map(|&n| self.graph[n].path.to_string_lossy().to_string())
+                        .collect();
+                    cycles.push(cycle);
+                } else if !finished[neighbor.index()] {
+                    visiting[neighbor.index()] = true;
+                    path_stack.push(neighbor);
+                    call_stack.push((
+                        neighbor,
+                        self.graph
+                            .neighbors_directed(neighbor, petgraph::Direction::Outgoing)
+                            .collect(),
+                        0,
+                    ));
+                }
+            }
+        }
+
+        dedup_cycles(cycles)
+    }
+}
+
+/// Rotate each cycle so its lexicographically smallest path is first, then
+/// drop duplicates — the same cycle can otherwise be found once per node it
+/// passes through.
+fn dedup_cycles(cycles: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    let mut seen = std::collections::HashSet::new();
+    let mut deduped = Vec::new();
+
+    for cycle in cycles {
+        let min_idx = cycle
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.cmp(b))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+
+        let mut rotated = cycle[min_idx..].to_vec();
+        rotated.extend_from_slice(&cycle[..min_idx]);
+
+        if seen.insert(rotated.clone()) {
+            deduped.push(rotated);
+        }
+    }
+
+    deduped
 }
 
 pub fn build_graph(files: &[PathBuf], root: &Path) -> ProjectGraph {
+    let dir_contents = DirContents::collect(root);
+    build_graph_with_aliases(files, root, &HashMap::new(), &dir_contents)
+}
+
+/// Same as [`build_graph`], but seeds the resolver's alias table with manual
+/// mappings (e.g. from a `doctype.json` manifest) and reads file contents
+/// and resolution candidates through a pre-built [`DirContents`] instead of
+/// probing the filesystem or `node_map` per import.
+pub fn build_graph_with_aliases(
+    files: &[PathBuf],
+    root: &Path,
+    manual_aliases: &HashMap<String, String>,
+    dir_contents: &DirContents,
+) -> ProjectGraph {
     let mut project_graph = ProjectGraph::new();
-    
+
     // Pre-populate nodes
     for file in files {
         project_graph.add_file(file.clone());
     }
 
     let import_regex = Regex::new(r#"(?:import\s+(?:[\w\s{},*]+from\s+)?|require\()['"]([^'"]+)['"]"#).unwrap();
+    let resolver = Resolver::new(root, dir_contents).with_manual_aliases(manual_aliases);
 
     for file_path in files {
         // Only process JS/TS/RS files for now
@@ -65,48 +203,20 @@ pub fn build_graph(files: &[PathBuf], root: &Path) -> ProjectGraph {
             continue;
         }
 
-        let full_path = root.join(file_path);
-        if let Ok(content) = fs::read_to_string(&full_path) {
-            for cap in import_regex.captures_iter(&content) {
+        if let Some(content) = dir_contents.read(file_path) {
+            for cap in import_regex.captures_iter(content.as_str()) {
                 if let Some(import_path) = cap.get(1) {
                     let import_str = import_path.as_str();
-                    
-                    // Simple resolution logic
-                    // 1. Ignore node_modules (non-relative imports) for now, or maybe track them differently?
-                    // For now, only track relative imports starting with .
-                    if import_str.starts_with('.') {
-                        let current_dir = file_path.parent().unwrap_or(Path::new(""));
-                        let resolved = current_dir.join(import_str);
-                        
-                        // Normalize (remove .. and .) - simplified for now
-                        // In a real implementation we need canonicalization, but that requires the file to exist.
-                        // Since we are working with relative paths inside the project, we can try to match against our file list.
-                        
-                        // Heuristic: try to find the matching file in our file list
-                        // This is O(N^2) effectively if we iterate, but with the map it's fast.
-                        // But we need to handle extensions (import './foo' -> './foo.ts')
-                        
-                        // Let's try to resolve it against known files
-                        // This logic needs to be robust.
-                         let candidates = vec![
-                            resolved.clone(),
-                            resolved.with_extension("ts"),
-                            resolved.with_extension("tsx"),
-                            resolved.with_extension("js"),
-                            resolved.with_extension("jsx"),
-                            resolved.join("index.ts"),
-                            resolved.join("index.js"),
-                        ];
-
-                        for candidate in candidates {
-                             // "normalize" candidate path to match how we store them (no leading ./ if possible)
-                             // Actually, let's just check if it exists in our node_map
-                             // We might need a more robust normalization here.
-                             if project_graph.node_map.contains_key(&candidate) {
-                                 project_graph.add_dependency(file_path.clone(), candidate);
-                                 break;
-                             }
-                        }
+
+                    if let Some(resolved) = resolver.resolve(import_str, file_path) {
+                        let is_external = resolved
+                            .components()
+                            .any(|c| c.as_os_str() == "node_modules");
+                        project_graph.add_dependency_with_kind(
+                            file_path.clone(),
+                            resolved,
+                            is_external,
+                        );
                     }
                 }
             }
@@ -115,3 +225,61 @@ pub fn build_graph(files: &[PathBuf], root: &Path) -> ProjectGraph {
 
     project_graph
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(name: &str) -> PathBuf {
+        PathBuf::from(name)
+    }
+
+    #[test]
+    fn no_cycle_in_acyclic_graph() {
+        let mut graph = ProjectGraph::new();
+        graph.add_dependency(path("a.ts"), path("b.ts"));
+        graph.add_dependency(path("b.ts"), path("c.ts"));
+
+        assert!(graph.find_cycles().is_empty());
+    }
+
+    #[test]
+    fn finds_two_node_cycle() {
+        let mut graph = ProjectGraph::new();
+        graph.add_dependency(path("a.ts"), path("b.ts"));
+        graph.add_dependency(path("b.ts"), path("a.ts"));
+
+        let cycles = graph.find_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 2);
+    }
+
+    #[test]
+    fn finds_three_node_cycle() {
+        let mut graph = ProjectGraph::new();
+        graph.add_dependency(path("a.ts"), path("b.ts"));
+        graph.add_dependency(path("b.ts"), path("c.ts"));
+        graph.add_dependency(path("c.ts"), path("a.ts"));
+
+        let cycles = graph.find_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 3);
+    }
+
+    #[test]
+    fn dedup_cycles_merges_rotations_of_the_same_cycle() {
+        let cycles = vec![
+            vec!["b".to_string(), "c".to_string(), "a".to_string()],
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            vec!["c".to_string(), "a".to_string(), "b".to_string()],
+        ];
+
+        let deduped = dedup_cycles(cycles);
+
+        assert_eq!(deduped.len(), 1);
+        assert_eq!(
+            deduped[0],
+            vec!["a".to_string(), "b".to_string(), "c".to_string()]
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use crate::dir_contents::DirContents;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A `doctype.json` at the project root, inspired by rust-analyzer's
+/// `rust-project.json`: a checked-in, deterministic override for the cases
+/// where automatic discovery gets the layout wrong (generated files, virtual
+/// roots, non-standard monorepos).
+#[derive(Debug, Default, Deserialize)]
+pub struct DoctypeManifest {
+    /// Source roots to crawl, relative to the manifest's directory. When
+    /// present, only these subtrees are walked instead of the whole project.
+    pub roots: Option<Vec<String>>,
+    /// Extra exclude globs, merged with the collector's own defaults
+    /// (`node_modules`, `.git`). Written relative to the project root (this
+    /// manifest's directory), even when `roots` restricts the crawl to
+    /// subtrees below it.
+    pub exclude: Option<Vec<String>>,
+    /// Extension filter for the crawl. Defaults to the context's own source
+    /// extensions when absent.
+    pub extensions: Option<Vec<String>>,
+    /// Manual module alias mappings (e.g. `"@/*": "src/*"`), seeded into the
+    /// resolver's alias table alongside whatever it infers from tsconfig.
+    pub aliases: Option<HashMap<String, String>>,
+}
+
+/// Load `doctype.json` from `root`, if one exists.
+pub fn load_manifest(dir_contents: &DirContents) -> Option<DoctypeManifest> {
+    let content = dir_contents.read(Path::new("doctype.json"))?;
+    serde_json::from_str(content.as_str()).ok()
+}
@@ -1,6 +1,34 @@
 use napi_derive::napi;
 use std::collections::HashMap;
 
+#[napi]
+pub enum Language {
+    Node,
+    Deno,
+    Rust,
+    Python,
+    Go,
+}
+
+impl From<crate::project_type::Language> for Language {
+    fn from(language: crate::project_type::Language) -> Self {
+        match language {
+            crate::project_type::Language::Node => Language::Node,
+            crate::project_type::Language::Deno => Language::Deno,
+            crate::project_type::Language::Rust => Language::Rust,
+            crate::project_type::Language::Python => Language::Python,
+            crate::project_type::Language::Go => Language::Go,
+        }
+    }
+}
+
+#[napi(object)]
+pub struct ProjectType {
+    pub language: Language,
+    pub marker_path: String,
+    pub monorepo: bool,
+}
+
 #[napi(object)]
 pub struct PackageJson {
     pub name: Option<String>,
@@ -22,6 +50,8 @@ pub struct FileContext {
 pub struct ProjectContext {
     pub files: Vec<FileContext>,
     pub package_json: Option<PackageJson>,
+    pub cycles: Vec<Vec<String>>,
+    pub project_type: Vec<ProjectType>,
 }
 
 #[napi]
@@ -43,8 +73,20 @@ pub fn get_project_context(root_path: String) -> ProjectContext {
         scripts: p.scripts,
     });
 
+    let napi_project_type = context
+        .project_type
+        .into_iter()
+        .map(|p| ProjectType {
+            language: p.language.into(),
+            marker_path: p.marker_path,
+            monorepo: p.monorepo,
+        })
+        .collect();
+
     ProjectContext {
         files: napi_files,
         package_json: napi_package_json,
+        cycles: context.cycles,
+        project_type: napi_project_type,
     }
 }
@@ -0,0 +1,140 @@
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Node,
+    Deno,
+    Rust,
+    Python,
+    Go,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectType {
+    pub language: Language,
+    pub marker_path: String,
+    pub monorepo: bool,
+}
+
+/// Classify a workspace by walking from `root` up through its ancestors and
+/// looking for marker files. A project can be polyglot, so every language
+/// whose marker turns up anywhere on the way is included; if the same
+/// language's marker appears at multiple levels, the one closest to `root`
+/// wins.
+pub fn detect_project_types(root: &Path) -> Vec<ProjectType> {
+    let mut detected = Vec::new();
+    let mut seen = HashSet::new();
+
+    for dir in root.ancestors() {
+        detect_marker(dir, "package.json", Language::Node, &mut seen, &mut detected, |content| {
+            content.contains("\"workspaces\"")
+        });
+        detect_marker(dir, "deno.json", Language::Deno, &mut seen, &mut detected, |_| false);
+        detect_marker(dir, "deno.jsonc", Language::Deno, &mut seen, &mut detected, |_| false);
+        detect_marker(dir, "Cargo.toml", Language::Rust, &mut seen, &mut detected, |content| {
+            content.contains("[workspace]")
+        });
+        detect_marker(dir, "pyproject.toml", Language::Python, &mut seen, &mut detected, |_| false);
+        detect_marker(dir, "requirements.txt", Language::Python, &mut seen, &mut detected, |_| false);
+        detect_marker(dir, "go.mod", Language::Go, &mut seen, &mut detected, |_| false);
+
+        if dir.join("pnpm-workspace.yaml").is_file() {
+            // Only patch the Node entry whose own marker lives in this same
+            // `dir` — otherwise a pnpm-workspace.yaml found in an unrelated
+            // ancestor could flag a Node entry detected elsewhere as a
+            // monorepo.
+            let package_json_marker = dir.join("package.json").to_string_lossy().to_string();
+            if let Some(node) = detected
+                .iter_mut()
+                .find(|p| p.language == Language::Node && p.marker_path == package_json_marker)
+            {
+                node.monorepo = true;
+            }
+        }
+    }
+
+    detected
+}
+
+fn detect_marker(
+    dir: &Path,
+    marker_name: &str,
+    language: Language,
+    seen: &mut HashSet<Language>,
+    detected: &mut Vec<ProjectType>,
+    is_monorepo: impl Fn(&str) -> bool,
+) {
+    if seen.contains(&language) {
+        return;
+    }
+
+    let marker_path = dir.join(marker_name);
+    if !marker_path.is_file() {
+        return;
+    }
+
+    let monorepo = fs::read_to_string(&marker_path)
+        .map(|content| is_monorepo(&content))
+        .unwrap_or(false);
+
+    seen.insert(language);
+    detected.push(ProjectType {
+        language,
+        marker_path: marker_path.to_string_lossy().to_string(),
+        monorepo,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static TEST_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    /// Build a throwaway project under the system temp dir with the given
+    /// files, returning its root. Callers are responsible for cleaning up
+    /// via `fs::remove_dir_all`.
+    fn project(files: &[(&str, &str)]) -> std::path::PathBuf {
+        let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let root = std::env::temp_dir().join(format!("doctype_project_type_test_{}_{id}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        for (relative_path, content) in files {
+            let full_path = root.join(relative_path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent).unwrap();
+            }
+            fs::write(full_path, content).unwrap();
+        }
+
+        root
+    }
+
+    #[test]
+    fn pnpm_workspace_flags_the_matching_package_json_as_monorepo() {
+        let root = project(&[("pnpm-workspace.yaml", ""), ("package.json", "{}")]);
+
+        let detected = detect_project_types(&root);
+
+        let node = detected.iter().find(|p| p.language == Language::Node).unwrap();
+        assert!(node.monorepo);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn pnpm_workspace_does_not_flag_an_unrelated_nested_package_json() {
+        let root = project(&[("pnpm-workspace.yaml", ""), ("apps/api/package.json", "{}")]);
+
+        let detected = detect_project_types(&root.join("apps/api"));
+
+        let node = detected.iter().find(|p| p.language == Language::Node).unwrap();
+        assert!(!node.monorepo);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
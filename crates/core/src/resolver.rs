@@ -0,0 +1,431 @@
+use crate::dir_contents::DirContents;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extensions tried, in order, when an import specifier doesn't resolve as-is.
+const RESOLVE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx"];
+
+#[derive(Debug, Deserialize, Default)]
+struct TsConfig {
+    #[serde(rename = "compilerOptions")]
+    compiler_options: Option<CompilerOptions>,
+    extends: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct CompilerOptions {
+    #[serde(rename = "baseUrl")]
+    base_url: Option<String>,
+    paths: Option<HashMap<String, Vec<String>>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AliasMap {
+    base_url: Option<PathBuf>,
+    paths: HashMap<String, Vec<String>>,
+}
+
+/// Resolves import specifiers the way Node/TypeScript would: relative paths
+/// against the importing file, bare specifiers against `tsconfig.json`
+/// `paths`/`baseUrl` aliases, and anything left against `node_modules`.
+///
+/// One `Resolver` is built per project root and reused across every file in
+/// that project, so the alias map is only loaded and parsed once per run.
+/// Existence checks and `package.json` reads go through a shared
+/// [`DirContents`] instead of hitting the filesystem or re-reading a file
+/// more than once.
+pub struct Resolver<'a> {
+    alias_map: AliasMap,
+    dir_contents: &'a DirContents,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(root: &Path, dir_contents: &'a DirContents) -> Self {
+        Self {
+            alias_map: load_alias_map(root),
+            dir_contents,
+        }
+    }
+
+    /// Seed (or override) the alias table with manual mappings, e.g. from a
+    /// `doctype.json` manifest. A manual mapping replaces a tsconfig/jsconfig
+    /// mapping with the same pattern; where different patterns both match an
+    /// import, which one applies is not defined.
+    pub fn with_manual_aliases(mut self, aliases: &HashMap<String, String>) -> Self {
+        for (pattern, target) in aliases {
+            self.alias_map
+                .paths
+                .insert(pattern.clone(), vec![target.clone()]);
+        }
+        self
+    }
+
+    /// Resolve `import_str` as seen from `importing_file` (relative to `root`).
+    /// Returns the resolved file's path relative to `root`, or `None` if it
+    /// couldn't be found anywhere we know to look.
+    pub fn resolve(&self, import_str: &str, importing_file: &Path) -> Option<PathBuf> {
+        if import_str.starts_with('.') {
+            let current_dir = importing_file.parent().unwrap_or_else(|| Path::new(""));
+            return self.resolve_as_file_or_dir(&current_dir.join(import_str));
+        }
+
+        if let Some(aliased) = self.resolve_alias(import_str) {
+            if let Some(found) = self.resolve_as_file_or_dir(&aliased) {
+                return Some(found);
+            }
+        }
+
+        self.resolve_node_modules(import_str)
+    }
+
+    /// Apply tsconfig `paths`/`baseUrl` to a bare specifier. Returns the
+    /// candidate path relative to `root`, not yet checked for existence.
+    fn resolve_alias(&self, import_str: &str) -> Option<PathBuf> {
+        for (pattern, targets) in &self.alias_map.paths {
+            if let Some(prefix) = pattern.strip_suffix('*') {
+                if let Some(rest) = import_str.strip_prefix(prefix) {
+                    if let Some(target) = targets.first() {
+                        let target = target.trim_end_matches('*');
+                        return Some(PathBuf::from(format!("{target}{rest}")));
+                    }
+                }
+            } else if pattern == import_str {
+                if let Some(target) = targets.first() {
+                    return Some(PathBuf::from(target));
+                }
+            }
+        }
+
+        self.alias_map.base_url.as_ref().map(|base| base.join(import_str))
+    }
+
+    /// Node/TS resolution order: the exact path, then each extension, then
+    /// `index.*` inside the path as a directory.
+    fn resolve_as_file_or_dir(&self, candidate: &Path) -> Option<PathBuf> {
+        let normalized = normalize(candidate);
+
+        if self.exists(&normalized) {
+            return Some(normalized);
+        }
+
+        for ext in RESOLVE_EXTENSIONS {
+            // Append rather than `with_extension`, which would turn
+            // `./foo.service` into `foo.ts` instead of `foo.service.ts`.
+            let mut with_ext = normalized.clone().into_os_string();
+            with_ext.push(format!(".{ext}"));
+            let with_ext = PathBuf::from(with_ext);
+            if self.exists(&with_ext) {
+                return Some(with_ext);
+            }
+        }
+
+        for ext in RESOLVE_EXTENSIONS {
+            let index = normalized.join(format!("index.{ext}"));
+            if self.exists(&index) {
+                return Some(index);
+            }
+        }
+
+        None
+    }
+
+    /// Resolve a bare specifier (e.g. `lodash` or `lodash/debounce`) against
+    /// `node_modules`, following `package.json` `exports`/`main`/`module` for
+    /// the package root itself.
+    fn resolve_node_modules(&self, import_str: &str) -> Option<PathBuf> {
+        let (package_name, subpath) = match import_str.split_once('/') {
+            // Scoped packages (`@scope/name`) keep the first two segments together.
+            Some((scope, rest)) if scope.starts_with('@') => match rest.split_once('/') {
+                Some((name, sub)) => (format!("{scope}/{name}"), Some(sub)),
+                None => (format!("{scope}/{rest}"), None),
+            },
+            Some((name, rest)) => (name.to_string(), Some(rest)),
+            None => (import_str.to_string(), None),
+        };
+
+        let package_dir = Path::new("node_modules").join(&package_name);
+        if !self.dir_contents.contains_dir(&package_dir) {
+            return None;
+        }
+
+        if let Some(subpath) = subpath {
+            return self.resolve_as_file_or_dir(&package_dir.join(subpath));
+        }
+
+        if let Some(entry) = self.package_entry_point(&package_dir) {
+            return self.resolve_as_file_or_dir(&package_dir.join(entry));
+        }
+
+        self.resolve_as_file_or_dir(&package_dir.join("index"))
+    }
+
+    fn package_entry_point(&self, package_dir: &Path) -> Option<String> {
+        let manifest_path = package_dir.join("package.json");
+        let content = self.dir_contents.read(&manifest_path)?;
+        let manifest: PackageManifest = serde_json::from_str(content.as_str()).ok()?;
+        exports_entry(manifest.exports.as_ref())
+            .or(manifest.main)
+            .or(manifest.module)
+    }
+
+    fn exists(&self, relative: &Path) -> bool {
+        self.dir_contents.contains(relative)
+    }
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct PackageManifest {
+    main: Option<String>,
+    module: Option<String>,
+    exports: Option<serde_json::Value>,
+}
+
+/// Resolve the `"."` entry of a package's `exports` map: a bare string, or an
+/// object keyed by condition (`import`/`default`/`require`, checked in that
+/// order since this crate only cares about reading source, never executing
+/// it). Subpath patterns (`"./*"`) aren't needed here — only the package
+/// root entry point matters for `resolve_node_modules`.
+fn exports_entry(exports: Option<&serde_json::Value>) -> Option<String> {
+    match exports? {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Object(map) => match map.get(".")? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Object(conditions) => ["import", "default", "require"]
+                .iter()
+                .find_map(|key| conditions.get(*key).and_then(|v| v.as_str()))
+                .map(str::to_string),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Load and merge `compilerOptions.paths`/`baseUrl` from `tsconfig.json` or
+/// `jsconfig.json` at `root`, following a single level of `extends`.
+fn load_alias_map(root: &Path) -> AliasMap {
+    let config_path = ["tsconfig.json", "jsconfig.json"]
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.exists());
+
+    let Some(config_path) = config_path else {
+        return AliasMap::default();
+    };
+
+    let Some(config) = read_tsconfig(&config_path) else {
+        return AliasMap::default();
+    };
+
+    let mut alias_map = AliasMap::default();
+
+    if let Some(extends) = &config.extends {
+        alias_map = load_alias_map_from(&root.join(extends));
+    }
+
+    if let Some(options) = config.compiler_options {
+        if let Some(base_url) = options.base_url {
+            alias_map.base_url = Some(PathBuf::from(base_url));
+        }
+        if let Some(paths) = options.paths {
+            alias_map.paths.extend(paths);
+        }
+    }
+
+    alias_map
+}
+
+fn load_alias_map_from(config_path: &Path) -> AliasMap {
+    let Some(config) = read_tsconfig(config_path) else {
+        return AliasMap::default();
+    };
+
+    let mut alias_map = AliasMap::default();
+    if let Some(options) = config.compiler_options {
+        if let Some(base_url) = options.base_url {
+            alias_map.base_url = Some(PathBuf::from(base_url));
+        }
+        if let Some(paths) = options.paths {
+            alias_map.paths.extend(paths);
+        }
+    }
+    alias_map
+}
+
+fn read_tsconfig(path: &Path) -> Option<TsConfig> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Collapse `.`/`..` components so resolved paths match how files are stored
+/// in the node map (no leading `./`, no `../` left unresolved where avoidable).
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    static TEST_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    /// Build a throwaway project under the system temp dir with the given
+    /// files, returning its root. Callers are responsible for cleaning up
+    /// via `fs::remove_dir_all`.
+    fn project(files: &[(&str, &str)]) -> PathBuf {
+        let id = TEST_COUNTER.fetch_add(1, Ordering::SeqCst);
+        let root = std::env::temp_dir().join(format!("doctype_resolver_test_{}_{id}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        for (relative_path, content) in files {
+            let full_path = root.join(relative_path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent).unwrap();
+            }
+            fs::write(full_path, content).unwrap();
+        }
+
+        root
+    }
+
+    #[test]
+    fn resolves_relative_import_with_missing_extension() {
+        let root = project(&[("src/a.ts", ""), ("src/b.ts", "")]);
+        let dir_contents = DirContents::collect(&root);
+        let resolver = Resolver::new(&root, &dir_contents);
+
+        let resolved = resolver.resolve("./b", Path::new("src/a.ts"));
+
+        assert_eq!(resolved, Some(PathBuf::from("src/b.ts")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn resolves_tsconfig_paths_wildcard_alias() {
+        let root = project(&[
+            ("tsconfig.json", r#"{"compilerOptions":{"paths":{"@/*":["src/*"]}}}"#),
+            ("src/components/x.ts", ""),
+        ]);
+        let dir_contents = DirContents::collect(&root);
+        let resolver = Resolver::new(&root, &dir_contents);
+
+        let resolved = resolver.resolve("@/components/x", Path::new("src/index.ts"));
+
+        assert_eq!(resolved, Some(PathBuf::from("src/components/x.ts")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn resolves_scoped_node_modules_package() {
+        let root = project(&[("node_modules/@scope/name/index.js", "")]);
+        let dir_contents = DirContents::collect(&root);
+        let resolver = Resolver::new(&root, &dir_contents);
+
+        let resolved = resolver.resolve("@scope/name", Path::new("src/a.ts"));
+
+        assert_eq!(resolved, Some(PathBuf::from("node_modules/@scope/name/index.js")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn resolves_node_modules_package_via_main_field() {
+        let root = project(&[
+            ("node_modules/lodash/package.json", r#"{"main":"lodash.js"}"#),
+            ("node_modules/lodash/lodash.js", ""),
+        ]);
+        let dir_contents = DirContents::collect(&root);
+        let resolver = Resolver::new(&root, &dir_contents);
+
+        let resolved = resolver.resolve("lodash", Path::new("src/a.ts"));
+
+        assert_eq!(resolved, Some(PathBuf::from("node_modules/lodash/lodash.js")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn resolves_node_modules_package_via_exports_string_form() {
+        let root = project(&[
+            ("node_modules/nanoid/package.json", r#"{"exports":"./index.js"}"#),
+            ("node_modules/nanoid/index.js", ""),
+        ]);
+        let dir_contents = DirContents::collect(&root);
+        let resolver = Resolver::new(&root, &dir_contents);
+
+        let resolved = resolver.resolve("nanoid", Path::new("src/a.ts"));
+
+        assert_eq!(resolved, Some(PathBuf::from("node_modules/nanoid/index.js")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn resolves_node_modules_package_via_exports_dot_condition_map() {
+        let root = project(&[
+            (
+                "node_modules/esm-only/package.json",
+                r#"{"exports":{".":{"import":"./esm/index.js","require":"./cjs/index.js"}}}"#,
+            ),
+            ("node_modules/esm-only/esm/index.js", ""),
+        ]);
+        let dir_contents = DirContents::collect(&root);
+        let resolver = Resolver::new(&root, &dir_contents);
+
+        let resolved = resolver.resolve("esm-only", Path::new("src/a.ts"));
+
+        assert_eq!(resolved, Some(PathBuf::from("node_modules/esm-only/esm/index.js")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn exports_takes_precedence_over_main_when_both_present() {
+        let root = project(&[
+            (
+                "node_modules/dual/package.json",
+                r#"{"main":"./legacy.js","exports":"./modern.js"}"#,
+            ),
+            ("node_modules/dual/modern.js", ""),
+            ("node_modules/dual/legacy.js", ""),
+        ]);
+        let dir_contents = DirContents::collect(&root);
+        let resolver = Resolver::new(&root, &dir_contents);
+
+        let resolved = resolver.resolve("dual", Path::new("src/a.ts"));
+
+        assert_eq!(resolved, Some(PathBuf::from("node_modules/dual/modern.js")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn resolves_node_modules_even_when_project_gitignores_it() {
+        let root = project(&[
+            (".gitignore", "node_modules/\n"),
+            ("node_modules/lodash/index.js", ""),
+        ]);
+        fs::create_dir_all(root.join(".git")).unwrap();
+        let dir_contents = DirContents::collect(&root);
+        let resolver = Resolver::new(&root, &dir_contents);
+
+        let resolved = resolver.resolve("lodash", Path::new("src/a.ts"));
+
+        assert_eq!(resolved, Some(PathBuf::from("node_modules/lodash/index.js")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn normalizes_dot_and_dot_dot_components() {
+        let normalized = normalize(Path::new("src/./components/../utils.ts"));
+        assert_eq!(normalized, PathBuf::from("src/utils.ts"));
+    }
+}